@@ -1,16 +1,18 @@
 use structopt::StructOpt;
 use structopt::clap::AppSettings::{ColorAuto, ColoredHelp};
 use clap::arg_enum;
-use anyhow::{Result, Context};
+use anyhow::{Result, Context, bail};
 use std::error::Error;
 use std::io::Write;
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
-use serde::export::Formatter;
-use core::fmt;
 use std::convert::TryFrom;
-use rocfl::{OcflObjectVersion, FileDetails, VersionId, OcflRepo, FsOcflRepo};
+use rocfl::{OcflObjectVersion, FileDetails, VersionId, FsOcflRepo};
 use std::cmp::Ordering;
-use chrono::{DateTime, Local};
+use std::collections::{HashMap, HashSet};
+use chrono::{DateTime, Local, Utc};
+use unicode_width::UnicodeWidthStr;
+use glob::Pattern;
+use serde_json::{Map, Value};
 
 #[derive(Debug, StructOpt)]
 #[structopt(name = "rocfl", author = "Peter Winckles <pwinckles@pm.me>")]
@@ -24,16 +26,44 @@ struct AppArgs {
     #[structopt(short, long)]
     quiet: bool,
 
+    /// Controls when to colorize output
+    #[structopt(long, value_name = "WHEN", possible_values = &ColorOption::variants(), default_value = "auto", case_insensitive = true)]
+    color: ColorOption,
+
     /// Subcommand to execute
     #[structopt(subcommand)]
     command: Command,
 }
 
+/// Wraps `termcolor::ColorChoice` so it can be parsed from `--color`; `ColorChoice`
+/// itself can't implement `FromStr` here because neither type is local to this crate.
+arg_enum! {
+    #[derive(Debug, Clone, Copy)]
+    enum ColorOption {
+        Auto,
+        Always,
+        Never,
+    }
+}
+
+impl ColorOption {
+    fn to_color_choice(&self) -> ColorChoice {
+        match self {
+            Self::Auto => ColorChoice::Auto,
+            Self::Always => ColorChoice::Always,
+            Self::Never => ColorChoice::Never,
+        }
+    }
+}
+
 /// A CLI for OCFL repositories.
 #[derive(Debug, StructOpt)]
 enum Command {
     #[structopt(name = "ls", author = "Peter Winckles <pwinckles@pm.me>")]
     List(List),
+
+    #[structopt(name = "diff", author = "Peter Winckles <pwinckles@pm.me>")]
+    Diff(Diff),
 }
 
 /// Lists objects or files within objects.
@@ -64,14 +94,49 @@ struct List {
     #[structopt(short, long)]
     reverse: bool,
 
+    /// Specifies how timestamps are displayed
+    #[structopt(long, value_name = "STYLE", possible_values = &TimeStyle::variants(), default_value = "long-iso", case_insensitive = true)]
+    time_style: TimeStyle,
+
+    /// Displays timestamps in UTC instead of local time
+    #[structopt(long)]
+    utc: bool,
+
+    /// Specifies the output format
+    #[structopt(long, value_name = "FORMAT", possible_values = &OutputFormat::variants(), default_value = "default", case_insensitive = true)]
+    output: OutputFormat,
+
+    /// Selects which fields appear in json/json-lines/null output. Defaults to all fields.
+    #[structopt(long, value_name = "FIELDS", use_delimiter = true, possible_values = &OutputField::variants(), case_insensitive = true)]
+    columns: Vec<OutputField>,
+
     // TODO need flag equiv of -d so that single objects can be listed
 
     /// ID of the object to list
     #[structopt(name = "OBJECT")]
     object_id: Option<String>,
 
-    // TODO path glob
+    /// Filters the object's contents to paths matching this glob pattern
+    #[structopt(name = "GLOB")]
+    path_glob: Option<String>,
+
+}
+
+/// Compares the state of two versions of an object.
+#[derive(Debug, StructOpt)]
+#[structopt(setting(ColorAuto), setting(ColoredHelp))]
+struct Diff {
+    /// ID of the object to diff
+    #[structopt(name = "OBJECT")]
+    object_id: String,
+
+    /// The earlier version to compare. Defaults to the version before RIGHT.
+    #[structopt(name = "LEFT")]
+    left: Option<u32>,
 
+    /// The later version to compare. Defaults to the object's head version.
+    #[structopt(name = "RIGHT")]
+    right: Option<u32>,
 }
 
 arg_enum! {
@@ -95,6 +160,134 @@ impl Field {
     }
 }
 
+/// Controls how `Listing::updated_str` renders a timestamp. `arg_enum!` can't
+/// express the hyphenated `long-iso` value, so `FromStr` is implemented by hand.
+#[derive(Debug, Clone, Copy)]
+enum TimeStyle {
+    Iso,
+    LongIso,
+    Full,
+    Relative,
+}
+
+impl TimeStyle {
+    fn variants() -> &'static [&'static str] {
+        &["iso", "long-iso", "full", "relative"]
+    }
+
+    /// Returns the `chrono` format string for this style, or `None` for
+    /// `Relative`, which is rendered by `relative_time_str` instead of a
+    /// fixed pattern.
+    fn format_str(&self) -> Option<&'static str> {
+        match self {
+            Self::Iso => Some("%Y-%m-%d"),
+            Self::LongIso => Some("%Y-%m-%d %H:%M:%S"),
+            Self::Full => Some("%Y-%m-%d %H:%M:%S %z"),
+            Self::Relative => None,
+        }
+    }
+}
+
+impl std::str::FromStr for TimeStyle {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "iso" => Ok(Self::Iso),
+            "long-iso" => Ok(Self::LongIso),
+            "full" => Ok(Self::Full),
+            "relative" => Ok(Self::Relative),
+            _ => Err(format!("Invalid time style: {}", value)),
+        }
+    }
+}
+
+/// Selects how `ls` renders its results. `arg_enum!` can't express the
+/// hyphenated `json-lines` value, so `FromStr` is implemented by hand, same
+/// as `TimeStyle`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OutputFormat {
+    /// The aligned, human-readable columnar grid.
+    Default,
+    /// A single JSON array of listing objects.
+    Json,
+    /// One JSON object per line.
+    JsonLines,
+    /// Tab-separated fields, NUL-separated records, so names with spaces survive piping.
+    Null,
+}
+
+impl OutputFormat {
+    fn variants() -> &'static [&'static str] {
+        &["default", "json", "json-lines", "null"]
+    }
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "default" => Ok(Self::Default),
+            "json" => Ok(Self::Json),
+            "json-lines" => Ok(Self::JsonLines),
+            "null" => Ok(Self::Null),
+            _ => Err(format!("Invalid output format: {}", value)),
+        }
+    }
+}
+
+/// A field that can be selected via `--columns` in structured output.
+/// `arg_enum!` can't express the hyphenated values (`storage-path`,
+/// `digest-algorithm`), so `FromStr` is implemented by hand, same as
+/// `TimeStyle`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OutputField {
+    Version,
+    Updated,
+    Name,
+    StoragePath,
+    DigestAlgorithm,
+    Digest,
+}
+
+impl OutputField {
+    fn variants() -> &'static [&'static str] {
+        &["version", "updated", "name", "storage-path", "digest-algorithm", "digest"]
+    }
+
+    fn default_fields() -> Vec<OutputField> {
+        vec![Self::Version, Self::Updated, Self::Name, Self::StoragePath, Self::DigestAlgorithm, Self::Digest]
+    }
+
+    fn key(&self) -> &'static str {
+        match self {
+            Self::Version => "version",
+            Self::Updated => "updated",
+            Self::Name => "name",
+            Self::StoragePath => "storage_path",
+            Self::DigestAlgorithm => "digest_algorithm",
+            Self::Digest => "digest",
+        }
+    }
+}
+
+impl std::str::FromStr for OutputField {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "version" => Ok(Self::Version),
+            "updated" => Ok(Self::Updated),
+            "name" => Ok(Self::Name),
+            "storage-path" => Ok(Self::StoragePath),
+            "digest-algorithm" => Ok(Self::DigestAlgorithm),
+            "digest" => Ok(Self::Digest),
+            _ => Err(format!("Invalid column: {}", value)),
+        }
+    }
+}
+
 fn main() {
     let args = AppArgs::from_args();
     let repo = FsOcflRepo::new(args.root.clone()
@@ -108,7 +301,8 @@ fn main() {
 
 fn exec_command(repo: &FsOcflRepo, args: &AppArgs) -> Result<()> {
     match &args.command {
-        Command::List(list) => list_command(&repo, &list, &args)?
+        Command::List(list) => list_command(&repo, &list, &args)?,
+        Command::Diff(diff) => diff_command(&repo, &diff, &args)?,
     }
     Ok(())
 }
@@ -117,7 +311,7 @@ fn list_command(repo: &FsOcflRepo, command: &List, args: &AppArgs) -> Result<()>
     if let Some(object_id) = &command.object_id {
         let version = parse_version(command.version)?;
         match repo.get_object(object_id, version.clone()) {
-            Ok(Some(object)) => print_object_contents(&object, command),
+            Ok(Some(object)) => print_object_contents(&object, command, args.color.to_color_choice())?,
             Ok(None) => {
                 match version {
                     Some(version) => println!("Object {} version {} was not found", object_id, version),
@@ -127,29 +321,43 @@ fn list_command(repo: &FsOcflRepo, command: &List, args: &AppArgs) -> Result<()>
             Err(e) => print_err(e.into(), args.quiet)
         }
     } else {
+        let mut objects = Vec::new();
+
         for object in repo.list_objects()
             .with_context(|| "Failed to list objects")? {
             match object {
-                Ok(object) => print_object(&object, command),
+                Ok(object) => objects.push(object),
                 Err(e) => print_err(e.into(), args.quiet)
             }
         }
+
+        print_objects(&objects, command, args.color.to_color_choice());
     }
 
     Ok(())
 }
 
-fn print_object(object: &OcflObjectVersion, command: &List) {
-    println!("{}", FormatListing {
-        listing: &Listing::from(object),
-        command
-    })
+fn print_objects(objects: &[OcflObjectVersion], command: &List, color: ColorChoice) {
+    let listings: Vec<Listing> = objects.iter().map(Listing::from).collect();
+    render_listings(&listings, command, color, ListingStyle::ObjectRecency);
 }
 
-fn print_object_contents(object: &OcflObjectVersion, command: &List) {
-    let mut listings: Vec<Listing> = object.state.iter().map(|(path, details)| {
-        Listing::new(path, details, &object.digest_algorithm)
-    }).collect();
+fn print_object_contents(object: &OcflObjectVersion, command: &List, color: ColorChoice) -> Result<()> {
+    let pattern = match &command.path_glob {
+        Some(path_glob) => Some(Pattern::new(path_glob)
+            .with_context(|| format!("Invalid glob pattern: {}", path_glob))?),
+        None => None,
+    };
+
+    let mut listings: Vec<Listing> = object.state.iter()
+        .filter(|(path, _)| pattern.as_ref().map_or(true, |pattern| pattern.matches(path)))
+        .map(|(path, details)| Listing::new(path, details, &object.digest_algorithm))
+        .collect();
+
+    if listings.is_empty() && pattern.is_some() && command.output == OutputFormat::Default {
+        println!("No files matched");
+        return Ok(());
+    }
 
     listings.sort_unstable_by(|a, b| {
         if command.reverse {
@@ -159,11 +367,295 @@ fn print_object_contents(object: &OcflObjectVersion, command: &List) {
         }
     });
 
-    for listing in listings {
-        println!("{}", FormatListing{
-            listing: &listing,
-            command
-        })
+    let style = ListingStyle::FileStatus { at_version: object.version.clone() };
+    render_listings(&listings, command, color, style);
+    Ok(())
+}
+
+/// Dispatches to the colorized grid renderer for the default format, or to
+/// the structured (`json`/`json-lines`/`null`) renderer otherwise.
+fn render_listings(listings: &[Listing], command: &List, color: ColorChoice, style: ListingStyle) {
+    match command.output {
+        OutputFormat::Default => print_listings(listings, command, color, style),
+        _ => print_structured_listings(listings, command),
+    }
+}
+
+fn print_structured_listings(listings: &[Listing], command: &List) {
+    let fields = if command.columns.is_empty() {
+        OutputField::default_fields()
+    } else {
+        command.columns.clone()
+    };
+
+    match command.output {
+        OutputFormat::Json => {
+            let values: Vec<Value> = listings.iter().map(|listing| listing_json(listing, &fields)).collect();
+            println!("{}", Value::Array(values));
+        },
+        OutputFormat::JsonLines => {
+            for listing in listings {
+                println!("{}", listing_json(listing, &fields));
+            }
+        },
+        OutputFormat::Null => {
+            for listing in listings {
+                let cells: Vec<String> = fields.iter().map(|field| field_str(listing, field)).collect();
+                print!("{}\0", cells.join("\t"));
+            }
+        },
+        OutputFormat::Default => unreachable!("print_structured_listings is never called for the default format"),
+    }
+}
+
+fn listing_json(listing: &Listing, fields: &[OutputField]) -> Value {
+    let mut map = Map::new();
+
+    for field in fields {
+        let value = match field {
+            OutputField::DigestAlgorithm if listing.digest_algorithm.is_none() => Value::Null,
+            OutputField::Digest if listing.digest.is_none() => Value::Null,
+            _ => Value::String(field_str(listing, field)),
+        };
+        map.insert(field.key().to_string(), value);
+    }
+
+    Value::Object(map)
+}
+
+fn field_str(listing: &Listing, field: &OutputField) -> String {
+    match field {
+        OutputField::Version => listing.version.version_str.clone(),
+        OutputField::Updated => listing.updated.to_rfc3339(),
+        OutputField::Name => listing.name.clone(),
+        OutputField::StoragePath => listing.storage_path.clone(),
+        OutputField::DigestAlgorithm => listing.digest_algorithm.cloned().unwrap_or_default(),
+        OutputField::Digest => listing.digest.cloned().unwrap_or_default(),
+    }
+}
+
+fn diff_command(repo: &FsOcflRepo, command: &Diff, args: &AppArgs) -> Result<()> {
+    let right_version = parse_version(command.right)?;
+
+    let right = match repo.get_object(&command.object_id, right_version) {
+        Ok(Some(object)) => object,
+        Ok(None) => {
+            println!("Object {} was not found", command.object_id);
+            return Ok(());
+        },
+        Err(e) => {
+            print_err(e.into(), args.quiet);
+            return Ok(());
+        }
+    };
+
+    let left_version = match command.left {
+        Some(left) => VersionId::try_from(left)?,
+        None => previous_version(&right.version)?,
+    };
+
+    let left = match repo.get_object(&command.object_id, Some(left_version.clone())) {
+        Ok(Some(object)) => object,
+        Ok(None) => {
+            println!("Object {} version {} was not found", command.object_id, left_version);
+            return Ok(());
+        },
+        Err(e) => {
+            print_err(e.into(), args.quiet);
+            return Ok(());
+        }
+    };
+
+    print_diff(&diff_states(&state_map(&left), &state_map(&right)), args.color.to_color_choice());
+
+    Ok(())
+}
+
+/// Extracts a logical path -> digest map from an object version's state, so
+/// `diff_states` can operate on plain data instead of the repo's types.
+fn state_map(object: &OcflObjectVersion) -> HashMap<String, String> {
+    object.state.iter()
+        .map(|(path, details)| (path.clone(), details.digest.clone()))
+        .collect()
+}
+
+fn previous_version(version: &VersionId) -> Result<VersionId> {
+    let number: u32 = version.version_str.trim_start_matches('v').parse()
+        .with_context(|| format!("Failed to parse version number from {}", version.version_str))?;
+
+    if number <= 1 {
+        bail!("Version {} has no preceding version", version.version_str);
+    }
+
+    Ok(VersionId::try_from(number - 1)?)
+}
+
+/// A single entry in the state diff between two versions of an object.
+struct Change {
+    kind: ChangeKind,
+    path: String,
+    previous_path: Option<String>,
+}
+
+enum ChangeKind {
+    Added,
+    Deleted,
+    Modified,
+    Renamed,
+    Copied,
+}
+
+impl ChangeKind {
+    fn sigil_and_color(&self) -> (char, Color) {
+        match self {
+            Self::Added => ('A', Color::Green),
+            Self::Deleted => ('D', Color::Red),
+            Self::Modified => ('M', Color::Yellow),
+            Self::Renamed => ('R', Color::Cyan),
+            Self::Copied => ('C', Color::Cyan),
+        }
+    }
+}
+
+/// Diffs two logical-path -> digest state maps. A path that disappears from
+/// one side and reappears on the other under the same digest is collapsed
+/// into a rename; if the original path is also still present unchanged, it's
+/// a copy instead.
+fn diff_states(left: &HashMap<String, String>, right: &HashMap<String, String>) -> Vec<Change> {
+    let mut added = Vec::new();
+    let mut deleted = Vec::new();
+    let mut modified = Vec::new();
+    let mut stable_digests: HashSet<&String> = HashSet::new();
+
+    for (path, digest) in right {
+        match left.get(path) {
+            Some(left_digest) if left_digest == digest => { stable_digests.insert(digest); },
+            Some(_) => modified.push(path.clone()),
+            None => added.push(path.clone()),
+        }
+    }
+
+    for path in left.keys() {
+        if !right.contains_key(path) {
+            deleted.push(path.clone());
+        }
+    }
+
+    let mut deleted_by_digest: HashMap<&String, Vec<String>> = HashMap::new();
+    for path in &deleted {
+        deleted_by_digest.entry(&left[path]).or_default().push(path.clone());
+    }
+
+    let mut renamed_from = HashSet::new();
+    let mut changes = Vec::new();
+
+    for path in added {
+        let digest = &right[&path];
+
+        if let Some(candidates) = deleted_by_digest.get_mut(digest) {
+            if let Some(previous) = candidates.pop() {
+                renamed_from.insert(previous.clone());
+                changes.push(Change { kind: ChangeKind::Renamed, path, previous_path: Some(previous) });
+                continue;
+            }
+        }
+
+        if stable_digests.contains(digest) {
+            changes.push(Change { kind: ChangeKind::Copied, path, previous_path: None });
+        } else {
+            changes.push(Change { kind: ChangeKind::Added, path, previous_path: None });
+        }
+    }
+
+    for path in deleted {
+        if !renamed_from.contains(&path) {
+            changes.push(Change { kind: ChangeKind::Deleted, path, previous_path: None });
+        }
+    }
+
+    for path in modified {
+        changes.push(Change { kind: ChangeKind::Modified, path, previous_path: None });
+    }
+
+    changes.sort_unstable_by_key(|change| change.path.clone());
+    changes
+}
+
+#[cfg(test)]
+mod diff_states_tests {
+    use super::*;
+
+    fn state(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(path, digest)| (path.to_string(), digest.to_string())).collect()
+    }
+
+    #[test]
+    fn detects_added_and_deleted() {
+        let left = state(&[("a.txt", "digest-a")]);
+        let right = state(&[("b.txt", "digest-b")]);
+
+        let changes = diff_states(&left, &right);
+
+        assert_eq!(changes.len(), 2);
+        assert!(matches!(changes[0].kind, ChangeKind::Deleted));
+        assert_eq!(changes[0].path, "a.txt");
+        assert!(matches!(changes[1].kind, ChangeKind::Added));
+        assert_eq!(changes[1].path, "b.txt");
+    }
+
+    #[test]
+    fn detects_modified() {
+        let left = state(&[("a.txt", "digest-1")]);
+        let right = state(&[("a.txt", "digest-2")]);
+
+        let changes = diff_states(&left, &right);
+
+        assert_eq!(changes.len(), 1);
+        assert!(matches!(changes[0].kind, ChangeKind::Modified));
+        assert_eq!(changes[0].path, "a.txt");
+    }
+
+    #[test]
+    fn collapses_rename_when_source_is_gone() {
+        let left = state(&[("old.txt", "digest-1")]);
+        let right = state(&[("new.txt", "digest-1")]);
+
+        let changes = diff_states(&left, &right);
+
+        assert_eq!(changes.len(), 1);
+        assert!(matches!(changes[0].kind, ChangeKind::Renamed));
+        assert_eq!(changes[0].path, "new.txt");
+        assert_eq!(changes[0].previous_path.as_deref(), Some("old.txt"));
+    }
+
+    #[test]
+    fn detects_copy_when_source_still_present() {
+        let left = state(&[("original.txt", "digest-1")]);
+        let right = state(&[("original.txt", "digest-1"), ("copy.txt", "digest-1")]);
+
+        let changes = diff_states(&left, &right);
+
+        assert_eq!(changes.len(), 1);
+        assert!(matches!(changes[0].kind, ChangeKind::Copied));
+        assert_eq!(changes[0].path, "copy.txt");
+        assert!(changes[0].previous_path.is_none());
+    }
+}
+
+fn print_diff(changes: &[Change], color: ColorChoice) {
+    let mut stdout = StandardStream::stdout(color);
+
+    for change in changes {
+        let (sigil, color) = change.kind.sigil_and_color();
+
+        let _ = stdout.set_color(ColorSpec::new().set_fg(Some(color)));
+        let _ = write!(&mut stdout, "{}", sigil);
+        let _ = stdout.reset();
+
+        match &change.previous_path {
+            Some(previous) => { let _ = writeln!(&mut stdout, "\t{} -> {}", previous, change.path); },
+            None => { let _ = writeln!(&mut stdout, "\t{}", change.path); },
+        }
     }
 }
 
@@ -204,10 +696,65 @@ impl<'a> Listing<'a> {
         }
     }
 
-    fn updated_str(&self) -> String {
-        self.updated.format("%Y-%m-%d %H:%M:%S").to_string()
+    fn updated_str(&self, command: &List) -> String {
+        let format = match command.time_style.format_str() {
+            Some(format) => format,
+            None => return relative_time_str(self.updated),
+        };
+
+        if command.utc {
+            self.updated.with_timezone(&Utc).format(format).to_string()
+        } else {
+            self.updated.format(format).to_string()
+        }
+    }
+
+}
+
+/// Renders a timestamp relative to now, e.g. "3 days ago" or "in 5 minutes".
+fn relative_time_str(updated: &DateTime<Local>) -> String {
+    let seconds = Local::now().signed_duration_since(*updated).num_seconds();
+
+    if seconds.abs() < 60 {
+        return String::from("just now");
+    }
+
+    let magnitude = largest_unit_str(seconds.abs());
+
+    if seconds >= 0 {
+        format!("{} ago", magnitude)
+    } else {
+        format!("in {}", magnitude)
     }
+}
 
+fn largest_unit_str(seconds: i64) -> String {
+    const MINUTE: i64 = 60;
+    const HOUR: i64 = 60 * MINUTE;
+    const DAY: i64 = 24 * HOUR;
+    const WEEK: i64 = 7 * DAY;
+    const MONTH: i64 = 30 * DAY;
+    const YEAR: i64 = 365 * DAY;
+
+    let (amount, unit) = if seconds >= YEAR {
+        (seconds / YEAR, "year")
+    } else if seconds >= MONTH {
+        (seconds / MONTH, "month")
+    } else if seconds >= WEEK {
+        (seconds / WEEK, "week")
+    } else if seconds >= DAY {
+        (seconds / DAY, "day")
+    } else if seconds >= HOUR {
+        (seconds / HOUR, "hour")
+    } else {
+        (seconds / MINUTE, "minute")
+    };
+
+    if amount == 1 {
+        format!("1 {}", unit)
+    } else {
+        format!("{} {}s", amount, unit)
+    }
 }
 
 impl<'a> From<&'a OcflObjectVersion> for Listing<'a> {
@@ -223,34 +770,194 @@ impl<'a> From<&'a OcflObjectVersion> for Listing<'a> {
     }
 }
 
-struct FormatListing<'a> {
-    listing: &'a Listing<'a>,
-    command: &'a List
+/// The columns that can appear in a listing grid, in display order.
+#[derive(Copy, Clone)]
+enum Column {
+    Version,
+    Updated,
+    Name,
+    Physical,
+    Digest,
 }
 
-impl<'a> fmt::Display for FormatListing<'a> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        // TODO figure out length for id
-        // TODO allow time to be formatted as UTC or local?
+#[derive(Copy, Clone, PartialEq)]
+enum Alignment {
+    Left,
+    Right,
+}
 
-        if self.command.long {
-            write!(f, "{version:>5}\t{updated:<19}\t{name:<42}",
-                   version = self.listing.version.version_str,  // For some reason the formatting is not applied to the output of VersionId::fmt()
-                   updated = self.listing.updated_str(),
-                   name = self.listing.name)?
-        } else {
-            write!(f, "{:<42}", self.listing.name)?
+impl Column {
+    fn alignment(&self) -> Alignment {
+        match self {
+            Self::Version => Alignment::Right,
+            _ => Alignment::Left,
+        }
+    }
+
+    fn value(&self, listing: &Listing, command: &List) -> String {
+        match self {
+            // For some reason the formatting is not applied to the output of VersionId::fmt()
+            Self::Version => listing.version.version_str.clone(),
+            Self::Updated => listing.updated_str(command),
+            Self::Name => listing.name.clone(),
+            Self::Physical => listing.storage_path.clone(),
+            Self::Digest => match (listing.digest_algorithm, listing.digest) {
+                (Some(algorithm), Some(digest)) => format!("{}:{}", algorithm, digest),
+                _ => String::new(),
+            }
+        }
+    }
+}
+
+/// Determines how a listing grid is colorized, mirroring exa's git-status
+/// column idea adapted to a listing's relationship to an OCFL version.
+enum ListingStyle {
+    /// Used when listing the objects in a repository: the version column is
+    /// colored according to how recently the object was updated.
+    ObjectRecency,
+    /// Used when listing an object's contents at a specific version: files
+    /// changed in that version render in green, carried-over files are dimmed.
+    FileStatus { at_version: VersionId },
+}
+
+/// A cell's color/intensity, independent of which terminal color it maps to.
+#[derive(Clone, Copy)]
+enum CellStyle {
+    Normal,
+    Dim,
+    Highlight(Color),
+}
+
+impl CellStyle {
+    fn to_spec(&self) -> ColorSpec {
+        let mut spec = ColorSpec::new();
+        match self {
+            Self::Normal => {},
+            Self::Dim => { spec.set_dimmed(true); },
+            Self::Highlight(color) => { spec.set_fg(Some(*color)); },
         }
+        spec
+    }
+}
+
+fn recency_style(updated: &DateTime<Local>) -> CellStyle {
+    let age = Local::now().signed_duration_since(*updated);
+
+    if age.num_hours() < 24 {
+        CellStyle::Highlight(Color::Green)
+    } else if age.num_days() >= 30 {
+        CellStyle::Dim
+    } else {
+        CellStyle::Normal
+    }
+}
 
-        if self.command.physical {
-            write!(f, "\t{}", self.listing.storage_path)?
+/// Computes the per-column color spec for one row, according to the active `ListingStyle`.
+fn row_specs(listing: &Listing, columns: &[Column], style: &ListingStyle) -> Vec<ColorSpec> {
+    match style {
+        ListingStyle::FileStatus { at_version } => {
+            let cell_style = if listing.version == at_version {
+                CellStyle::Highlight(Color::Green)
+            } else {
+                CellStyle::Dim
+            };
+            columns.iter().map(|_| cell_style.to_spec()).collect()
+        },
+        ListingStyle::ObjectRecency => {
+            columns.iter().map(|column| match column {
+                Column::Version => recency_style(listing.updated).to_spec(),
+                _ => CellStyle::Normal.to_spec(),
+            }).collect()
+        },
+    }
+}
+
+/// Determines which columns are active for this invocation, based on the
+/// flags that were set and what data is actually available to print.
+fn active_columns(command: &List, listings: &[Listing]) -> Vec<Column> {
+    let mut columns = Vec::new();
+
+    if command.long {
+        columns.push(Column::Version);
+        columns.push(Column::Updated);
+    }
+
+    columns.push(Column::Name);
+
+    if command.physical {
+        columns.push(Column::Physical);
+    }
+
+    if command.digest && listings.iter().any(|listing| listing.digest.is_some()) {
+        columns.push(Column::Digest);
+    }
+
+    columns
+}
+
+/// Renders a grid of listings: the active columns are used to build a row
+/// per listing, and `print_grid` pads every cell to its column's max
+/// display width, keeping output aligned regardless of value length.
+fn print_listings(listings: &[Listing], command: &List, color: ColorChoice, style: ListingStyle) {
+    let columns = active_columns(command, listings);
+    let alignments: Vec<Alignment> = columns.iter().map(Column::alignment).collect();
+
+    let rows: Vec<Vec<String>> = listings.iter()
+        .map(|listing| columns.iter().map(|column| column.value(listing, command)).collect())
+        .collect();
+
+    let specs: Vec<Vec<ColorSpec>> = listings.iter()
+        .map(|listing| row_specs(listing, &columns, &style))
+        .collect();
+
+    print_grid(&rows, &alignments, &specs, color);
+}
+
+/// Two-pass grid renderer used by the `ls` subcommand: the first
+/// pass measures the max display width of each column, the second writes
+/// every cell padded to that width, colored per `specs`, via a `StandardStream`.
+fn print_grid(rows: &[Vec<String>], alignments: &[Alignment], specs: &[Vec<ColorSpec>], color: ColorChoice) {
+    if alignments.is_empty() {
+        return;
+    }
+
+    let mut widths = vec![0usize; alignments.len()];
+    for row in rows {
+        for (i, cell) in row.iter().enumerate() {
+            widths[i] = widths[i].max(UnicodeWidthStr::width(cell.as_str()));
         }
+    }
 
-        if self.command.digest && self.listing.digest.is_some() {
-            write!(f, "\t{}:{}", self.listing.digest_algorithm.unwrap(), self.listing.digest.unwrap())?
+    let last = alignments.len() - 1;
+    let mut stdout = StandardStream::stdout(color);
+
+    for (row, row_specs) in rows.iter().zip(specs) {
+        for (i, cell) in row.iter().enumerate() {
+            if i > 0 {
+                let _ = write!(&mut stdout, "  ");
+            }
+
+            // A left-aligned final column is not padded so lines don't end in whitespace.
+            let padded = if i == last && alignments[i] == Alignment::Left {
+                cell.clone()
+            } else {
+                pad(cell, widths[i], alignments[i])
+            };
+
+            let _ = stdout.set_color(&row_specs[i]);
+            let _ = write!(&mut stdout, "{}", padded);
+            let _ = stdout.reset();
         }
 
-        Ok(())
+        let _ = writeln!(&mut stdout);
+    }
+}
+
+fn pad(value: &str, width: usize, alignment: Alignment) -> String {
+    let padding = " ".repeat(width.saturating_sub(UnicodeWidthStr::width(value)));
+    match alignment {
+        Alignment::Left => format!("{}{}", value, padding),
+        Alignment::Right => format!("{}{}", padding, value),
     }
 }
 